@@ -8,8 +8,7 @@ fn main() {
     let credential = Credential::new(ak, sk);
     let signer = Signer::new(credential, "vm".to_string(), "cn-north-1".to_string());
 
-    let mut req = Request::builder();
-    let mut req = req.method("GET")
+    let mut req = Request::builder().method("GET")
         .uri("https://vm.jdcloud-api.com/v1/regions/cn-north-1/instances")
         .body("".to_string()).unwrap();
     signer.sign_request(&mut req).unwrap();
@@ -1,5 +1,7 @@
-use http;
-use reqwest::{self, Response, Error};
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{self, Method, Response, Error, Url};
+
+use crate::signer::Signer;
 
 #[derive(Default)]
 pub struct Client {
@@ -19,7 +21,7 @@ impl Client {
             .headers(request.headers().clone())
             .body(request.body().clone())
             .send();
-        return resp
+        resp
     }
 
     pub async fn execute_async(&self,request: http::Request<String>) -> Result<Response,Error>{
@@ -33,3 +35,40 @@ impl Client {
         Ok(resp)
     }
 }
+
+impl Signer {
+    /// Signs a request that is about to be built with a
+    /// `reqwest::RequestBuilder` and returns the headers to chain onto it.
+    pub fn sign_reqwest(&self, method: Method, url: &Url, body: &str) -> Result<Vec<(HeaderName, HeaderValue)>, crate::Error> {
+        let request = http::Request::builder()
+            .method(method)
+            .uri(url.as_str())
+            .body(body.to_string())
+            .map_err(crate::Error::new_invalid_request)?;
+        let headers = self.sign_request_with_overlay(&request, &HeaderMap::new())?;
+        Ok(headers.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credential::Credential;
+
+    #[test]
+    fn test_sign_reqwest() {
+        let s = Signer::new(Credential::new("ak", "sk"), "vm", "cn-north-1");
+        let url = Url::parse("https://vm.jdcloud-api.com/v1/regions/cn-north-1/instances").unwrap();
+        let headers = s.sign_reqwest(Method::GET, &url, "").unwrap();
+        let mut names: Vec<_> = headers.iter().map(|x| x.0.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["authorization", "user-agent", "x-jdcloud-date", "x-jdcloud-nonce"]);
+
+        let builder = headers.iter().fold(reqwest::blocking::Client::new().get(url.clone()),
+                                          |b, (k, v)| b.header(k, v));
+        let request = builder.build().unwrap();
+        let mut signed = http::Request::builder().method("GET").uri(url.as_str()).body("".to_string()).unwrap();
+        signed.headers_mut().extend(request.headers().clone());
+        assert!(s.verify_request(&signed).unwrap());
+    }
+}
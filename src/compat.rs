@@ -0,0 +1,34 @@
+/// Which implementation the canonical request should match byte for byte
+/// where JD Cloud SDKs disagree.
+///
+/// The known divergences from `Spec`:
+///
+/// - `JavaSdk` writes query parameters with an empty value as a bare name,
+///   so `?a&b=1` canonicalizes to `a&b=1` instead of `a=&b=1`.
+/// - `GoSdk` keeps a `+` in the query as a literal plus, so `?a=+`
+///   canonicalizes to `a=%2B` instead of `a=%20`.
+/// - `GoSdk` also trims and collapses tabs in header values like spaces, so
+///   `"\ta\t\tb"` canonicalizes to `a b` instead of being kept verbatim.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JdCloudSdkCompat {
+    /// The published signing spec, the default.
+    #[default]
+    Spec,
+    JavaSdk,
+    GoSdk,
+}
+
+impl JdCloudSdkCompat {
+    pub(crate) fn omits_empty_value_equals(self) -> bool {
+        self == JdCloudSdkCompat::JavaSdk
+    }
+
+    pub(crate) fn keeps_literal_plus(self) -> bool {
+        self == JdCloudSdkCompat::GoSdk
+    }
+
+    pub(crate) fn trims_tabs(self) -> bool {
+        self == JdCloudSdkCompat::GoSdk
+    }
+}
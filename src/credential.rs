@@ -1,3 +1,6 @@
+#[cfg(feature="zeroize")]
+use zeroize::Zeroize;
+
 pub struct Credential {
     ak: String,
     sk: String
@@ -24,8 +27,37 @@ impl Credential {
     pub fn sk(&self) -> &str {
         &self.sk
     }
+
+    /// Replaces the key pair. With the `zeroize` feature the old secret is
+    /// wiped from memory before it is released.
+    pub fn rotate<S>(&mut self, ak: S, sk: S) -> &mut Credential
+        where S: Into<String>
+    {
+        #[cfg(feature="zeroize")]
+        self.sk.zeroize();
+        self.ak = ak.into();
+        self.sk = sk.into();
+        self
+    }
 }
 
+#[cfg(feature="zeroize")]
+impl Zeroize for Credential {
+    fn zeroize(&mut self) {
+        self.sk.zeroize();
+    }
+}
+
+#[cfg(feature="zeroize")]
+impl Drop for Credential {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature="zeroize")]
+impl zeroize::ZeroizeOnDrop for Credential {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +78,24 @@ mod tests {
         assert!(!Credential::new("".to_string(), "b".to_string()).is_valid());
         assert!(!Credential::new("".to_string(), "".to_string()).is_valid());
     }
+
+    #[test]
+    fn test_rotate() {
+        let mut c = Credential::new("a", "b");
+        c.rotate("c", "d");
+        assert_eq!(c.ak(), "c");
+        assert_eq!(c.sk(), "d");
+    }
+
+    #[cfg(feature="zeroize")]
+    #[test]
+    fn test_zeroize() {
+        fn assert_zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<Credential>();
+
+        let mut c = Credential::new("a", "b");
+        c.zeroize();
+        assert_eq!(c.sk(), "");
+        assert!(!c.is_valid());
+    }
 }
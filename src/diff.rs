@@ -0,0 +1,119 @@
+/// A field of a canonical request.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CanonicalField {
+    Method,
+    Path,
+    Query,
+    Headers,
+    SignedHeaders,
+    PayloadHash,
+}
+
+/// The first field where two canonical requests differ, with its value in
+/// each. For `Headers` the values are the first differing header lines, an
+/// empty string standing for a line one side doesn't have.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CanonicalDiff {
+    pub field: CanonicalField,
+    pub left: String,
+    pub right: String,
+}
+
+struct Parts<'a> {
+    method: &'a str,
+    path: &'a str,
+    query: &'a str,
+    headers: Vec<&'a str>,
+    signed_headers: &'a str,
+    payload_hash: &'a str,
+}
+
+/// Splits a canonical request on `\n`. The header lines run up to the
+/// first empty line. Missing lines read as empty, so truncated input from an
+/// error response still compares.
+fn split(canonical: &str) -> Parts<'_> {
+    let mut lines = canonical.split('\n');
+    let method = lines.next().unwrap_or("");
+    let path = lines.next().unwrap_or("");
+    let query = lines.next().unwrap_or("");
+    let headers = lines.by_ref().take_while(|x| !x.is_empty()).collect();
+    Parts {
+        method,
+        path,
+        query,
+        headers,
+        signed_headers: lines.next().unwrap_or(""),
+        payload_hash: lines.next().unwrap_or(""),
+    }
+}
+
+/// Compares two canonical requests, e.g. the one in a server's signature
+/// mismatch response and your own, field by field and returns the first
+/// difference, or `None` if they're the same.
+pub fn diff_canonical(a: &str, b: &str) -> Option<CanonicalDiff> {
+    let (a, b) = (split(a), split(b));
+    let diff = |field, left: &str, right: &str| Some(CanonicalDiff {
+        field,
+        left: left.to_string(),
+        right: right.to_string(),
+    });
+
+    for (field, left, right) in [
+        (CanonicalField::Method, a.method, b.method),
+        (CanonicalField::Path, a.path, b.path),
+        (CanonicalField::Query, a.query, b.query),
+    ] {
+        if left != right {
+            return diff(field, left, right)
+        }
+    }
+    for i in 0..a.headers.len().max(b.headers.len()) {
+        let left = a.headers.get(i).copied().unwrap_or("");
+        let right = b.headers.get(i).copied().unwrap_or("");
+        if left != right {
+            return diff(CanonicalField::Headers, left, right)
+        }
+    }
+    if a.signed_headers != b.signed_headers {
+        return diff(CanonicalField::SignedHeaders, a.signed_headers, b.signed_headers)
+    }
+    if a.payload_hash != b.payload_hash {
+        return diff(CanonicalField::PayloadHash, a.payload_hash, b.payload_hash)
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static CANONICAL: &str = "GET\n/v1/regions/cn-north-1/instances\npageNumber=2&pageSize=10\n\
+                              content-type:application/json\nx-jdcloud-date:20180405T010203Z\n\n\
+                              content-type;x-jdcloud-date\n\
+                              e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    #[test]
+    fn test_diff_canonical_query() {
+        assert_eq!(diff_canonical(CANONICAL, CANONICAL), None);
+
+        let other = CANONICAL.replace("pageNumber=2&pageSize=10", "pageNumber=2&pageSize=20");
+        assert_eq!(diff_canonical(CANONICAL, &other), Some(CanonicalDiff {
+            field: CanonicalField::Query,
+            left: "pageNumber=2&pageSize=10".to_string(),
+            right: "pageNumber=2&pageSize=20".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_diff_canonical_headers() {
+        let other = CANONICAL.replace("content-type:application/json\n", "");
+        let diff = diff_canonical(CANONICAL, &other).unwrap();
+        assert_eq!(diff.field, CanonicalField::Headers);
+        assert_eq!(diff.left, "content-type:application/json");
+        assert_eq!(diff.right, "x-jdcloud-date:20180405T010203Z");
+
+        let diff = diff_canonical(CANONICAL, "GET\n/v1/regions/cn-north-1/instances").unwrap();
+        assert_eq!(diff.field, CanonicalField::Query);
+        assert_eq!(diff.right, "");
+    }
+}
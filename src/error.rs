@@ -1,5 +1,6 @@
 use std::error::Error as StdError;
 use std::fmt;
+use std::io;
 
 type Cause = Box<dyn StdError + Send + Sync>;
 
@@ -12,11 +13,23 @@ pub struct Error {
 struct ErrorImpl {
     kind: Kind,
     cause: Option<Cause>,
+    field: Option<String>,
 }
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum Kind {
     InvalidCredential,
+    MalformedAuthorization,
+    MalformedDate,
+    UppercaseHeader,
+    InvalidHeader,
+    InvalidRequest,
+    InvalidExpires,
+    MissingHost,
+    HeadersTooLarge,
+    ClockTooOld,
+    SelfCheckFailed,
+    Io,
 }
 
 impl Error {
@@ -25,6 +38,7 @@ impl Error {
             inner: Box::new(ErrorImpl {
                 kind,
                 cause,
+                field: None,
             }),
         }
     }
@@ -32,26 +46,184 @@ impl Error {
     pub(crate) fn new_invalid_credential() -> Error {
         Error::new(Kind::InvalidCredential, None)
     }
+
+    pub(crate) fn new_malformed_authorization() -> Error {
+        Error::new(Kind::MalformedAuthorization, None)
+    }
+
+    pub(crate) fn new_malformed_date<E>(cause: E) -> Error
+        where E: Into<Cause>
+    {
+        Error::new(Kind::MalformedDate, Some(cause.into()))
+    }
+
+    /// Records the header name or query key the error is about. It must
+    /// never be a header value or anything else that could hold a secret.
+    pub(crate) fn with_field(mut self, field: &str) -> Error {
+        self.inner.field = Some(field.to_string());
+        self
+    }
+
+    pub(crate) fn new_uppercase_header(name: &str) -> Error {
+        Error::new(Kind::UppercaseHeader, Some(name.into())).with_field(name)
+    }
+
+    pub(crate) fn new_invalid_header(name: &str, reason: &str) -> Error {
+        Error::new(Kind::InvalidHeader, Some(format!("`{}`: {}", name, reason).into())).with_field(name)
+    }
+
+    pub(crate) fn new_invalid_request<E>(cause: E) -> Error
+        where E: Into<Cause>
+    {
+        Error::new(Kind::InvalidRequest, Some(cause.into()))
+    }
+
+    pub(crate) fn new_invalid_expires() -> Error {
+        Error::new(Kind::InvalidExpires, None)
+    }
+
+    pub(crate) fn new_missing_host() -> Error {
+        Error::new(Kind::MissingHost, None)
+    }
+
+    pub(crate) fn new_headers_too_large(size: usize, limit: usize) -> Error {
+        Error::new(Kind::HeadersTooLarge, Some(format!("{} bytes, limit is {}", size, limit).into()))
+    }
+
+    pub(crate) fn new_clock_too_old() -> Error {
+        Error::new(Kind::ClockTooOld, None)
+    }
+
+    pub(crate) fn new_self_check_failed() -> Error {
+        Error::new(Kind::SelfCheckFailed, None)
+    }
+
+    pub(crate) fn new_io(cause: io::Error) -> Error {
+        Error::new(Kind::Io, Some(cause.into()))
+    }
+
+    pub fn is_invalid_credential(&self) -> bool {
+        self.inner.kind == Kind::InvalidCredential
+    }
+
+    pub fn is_malformed_authorization(&self) -> bool {
+        self.inner.kind == Kind::MalformedAuthorization
+    }
+
+    pub fn is_malformed_date(&self) -> bool {
+        self.inner.kind == Kind::MalformedDate
+    }
+
+    pub fn is_uppercase_header(&self) -> bool {
+        self.inner.kind == Kind::UppercaseHeader
+    }
+
+    pub fn is_invalid_header(&self) -> bool {
+        self.inner.kind == Kind::InvalidHeader
+    }
+
+    pub fn is_invalid_request(&self) -> bool {
+        self.inner.kind == Kind::InvalidRequest
+    }
+
+    pub fn is_invalid_expires(&self) -> bool {
+        self.inner.kind == Kind::InvalidExpires
+    }
+
+    pub fn is_missing_host(&self) -> bool {
+        self.inner.kind == Kind::MissingHost
+    }
+
+    pub fn is_headers_too_large(&self) -> bool {
+        self.inner.kind == Kind::HeadersTooLarge
+    }
+
+    pub fn is_clock_too_old(&self) -> bool {
+        self.inner.kind == Kind::ClockTooOld
+    }
+
+    pub fn is_self_check_failed(&self) -> bool {
+        self.inner.kind == Kind::SelfCheckFailed
+    }
+
+    pub fn is_io(&self) -> bool {
+        self.inner.kind == Kind::Io
+    }
+
+    /// The header name or query key that caused the error, where there is
+    /// one.
+    pub fn field(&self) -> Option<&str> {
+        self.inner.field.as_deref()
+    }
 }
 
-impl StdError for Error {
-    fn description(&self) -> &str {
+impl Error {
+    fn description_str(&self) -> &str {
         match self.inner.kind {
             Kind::InvalidCredential => "invalid credential",
+            Kind::MalformedAuthorization => "malformed authorization header",
+            Kind::MalformedDate => "malformed x-jdcloud-date header",
+            Kind::UppercaseHeader => "header name is not lowercase",
+            Kind::InvalidHeader => "invalid header",
+            Kind::InvalidRequest => "invalid request",
+            Kind::InvalidExpires => "presign expiry must be between 1 second and 7 days",
+            Kind::MissingHost => "request has no host",
+            Kind::HeadersTooLarge => "signed headers too large",
+            Kind::ClockTooOld => "system clock is before the minimum valid date",
+            Kind::SelfCheckFailed => "signed request failed verification",
+            Kind::Io => "i/o error",
         }
     }
+}
 
-    fn cause(&self) -> Option<&dyn StdError> {
-        self.inner.cause.as_ref().map(|cause| &**cause as &dyn StdError)
+impl StdError for Error {
+    fn description(&self) -> &str {
+        self.description_str()
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.inner.cause.as_ref().map(|cause| &**cause as &(dyn StdError + 'static))
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let Some(ref cause) = self.inner.cause {
-            write!(f, "{}: {}", self.description(), cause)
+            write!(f, "{}: {}", self.description_str(), cause)
         } else {
-            f.write_str(self.description())
+            f.write_str(self.description_str())
         }
     }
 }
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::new_io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source() {
+        let err = Error::from(io::Error::new(io::ErrorKind::UnexpectedEof, "credentials file truncated"));
+        assert!(err.is_io());
+        assert_eq!(err.to_string(), "i/o error: credentials file truncated");
+        let source = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.kind(), io::ErrorKind::UnexpectedEof);
+
+        assert!(Error::new_invalid_credential().source().is_none());
+    }
+
+    #[test]
+    fn test_field() {
+        let err = Error::new_invalid_header("x-foo", "value is not visible ASCII");
+        assert!(err.is_invalid_header());
+        assert_eq!(err.field(), Some("x-foo"));
+        assert_eq!(err.to_string(), "invalid header: `x-foo`: value is not visible ASCII");
+        assert_eq!(Error::new_uppercase_header("X-Foo").field(), Some("X-Foo"));
+        assert!(Error::new_missing_host().field().is_none());
+    }
+}
@@ -1,12 +1,28 @@
 #![deny(bare_trait_objects)]
 
 mod signer;
+mod compat;
 mod credential;
+mod diff;
 mod error;
+mod nonce;
+mod scheme;
+mod sign_ext;
+mod signed;
+mod signed_request;
 
-pub use signer::Signer;
+pub use signer::{SignatureParams, Signer, SignerConfig, SigningMode};
+pub use compat::JdCloudSdkCompat;
 pub use credential::Credential;
+pub use diff::{diff_canonical, CanonicalDiff, CanonicalField};
 pub use error::Error;
+pub use nonce::{CounterNonce, NonceGenerator, UuidFormat, UuidNonce};
+#[cfg(feature="rand_core")]
+pub use nonce::RngUuidNonce;
+pub use scheme::{DigestAlgorithm, SignatureEncoding, SigningScheme};
+pub use sign_ext::SignExt;
+pub use signed::Signed;
+pub use signed_request::SignedRequest;
 
 #[cfg(feature="reqwest")]
 mod client;
@@ -0,0 +1,193 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature="rand_core")]
+use std::sync::Mutex;
+
+#[cfg(feature="rand_core")]
+use rand_core::RngCore;
+use uuid::Uuid;
+
+/// Produces the value of the `x-jdcloud-nonce` header for each signature.
+pub trait NonceGenerator: Send + Sync {
+    fn generate(&self) -> String;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum UuidFormat {
+    /// `55f3919e-3a7d-4174-b117-f150ff25e274`
+    #[default]
+    Hyphenated,
+    /// `55f3919e3a7d4174b117f150ff25e274`
+    Simple,
+}
+
+/// Random (v4) UUID nonces, the default.
+#[derive(Clone, Debug, Default)]
+pub struct UuidNonce {
+    format: UuidFormat,
+}
+
+impl UuidNonce {
+    pub fn new(format: UuidFormat) -> UuidNonce {
+        UuidNonce {
+            format,
+        }
+    }
+}
+
+impl NonceGenerator for UuidNonce {
+    fn generate(&self) -> String {
+        self.format.format(Uuid::new_v4())
+    }
+}
+
+impl UuidFormat {
+    fn format(self, uuid: Uuid) -> String {
+        match self {
+            UuidFormat::Hyphenated => uuid.to_hyphenated().to_string(),
+            UuidFormat::Simple => uuid.to_simple().to_string(),
+        }
+    }
+}
+
+/// Random (v4) UUID nonces drawn from a caller-supplied RNG instead of the
+/// system one, for reproducible tests or a mandated CSPRNG.
+#[cfg(feature="rand_core")]
+#[derive(Debug)]
+pub struct RngUuidNonce<R> {
+    rng: Mutex<R>,
+    format: UuidFormat,
+}
+
+#[cfg(feature="rand_core")]
+impl<R: RngCore> RngUuidNonce<R> {
+    pub fn new(rng: R, format: UuidFormat) -> RngUuidNonce<R> {
+        RngUuidNonce {
+            rng: Mutex::new(rng),
+            format,
+        }
+    }
+}
+
+#[cfg(feature="rand_core")]
+impl<R: RngCore + Send> NonceGenerator for RngUuidNonce<R> {
+    fn generate(&self) -> String {
+        let mut bytes = [0; 16];
+        self.rng.lock().unwrap_or_else(|x| x.into_inner()).fill_bytes(&mut bytes);
+        let uuid = uuid::Builder::from_bytes(bytes)
+            .set_variant(uuid::Variant::RFC4122)
+            .set_version(uuid::Version::Random)
+            .build();
+        self.format.format(uuid)
+    }
+}
+
+/// Monotonically increasing, zero-padded decimal nonces for services that
+/// reject replays by comparing against the last nonce seen.
+#[derive(Debug)]
+pub struct CounterNonce {
+    next: AtomicU64,
+    width: usize,
+}
+
+impl CounterNonce {
+    /// Counts up from `start`, padded to 20 digits, the width of `u64::MAX`.
+    pub fn new(start: u64) -> CounterNonce {
+        CounterNonce {
+            next: AtomicU64::new(start),
+            width: 20,
+        }
+    }
+
+    /// Pad to `width` digits instead. Values that need more digits aren't
+    /// truncated.
+    pub fn with_width(mut self, width: usize) -> CounterNonce {
+        self.width = width;
+        self
+    }
+}
+
+impl Default for CounterNonce {
+    fn default() -> CounterNonce {
+        CounterNonce::new(1)
+    }
+}
+
+impl NonceGenerator for CounterNonce {
+    fn generate(&self) -> String {
+        let value = self.next.fetch_add(1, Ordering::SeqCst);
+        format!("{:0width$}", value, width = self.width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Credential, Signer};
+
+    #[test]
+    fn test_uuid_nonce() {
+        let nonce = UuidNonce::default().generate();
+        assert_eq!(nonce.len(), 36);
+        assert!(Uuid::parse_str(&nonce).is_ok());
+
+        let nonce = UuidNonce::new(UuidFormat::Simple).generate();
+        assert_eq!(nonce.len(), 32);
+        assert!(nonce.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(nonce, UuidNonce::new(UuidFormat::Simple).generate());
+    }
+
+    #[cfg(feature="rand_core")]
+    #[test]
+    fn test_rng_uuid_nonce() {
+        /// A seeded xorshift generator, so the nonces are the same on every run.
+        struct XorShift(u64);
+
+        impl RngCore for XorShift {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                self.0 ^= self.0 << 13;
+                self.0 ^= self.0 >> 7;
+                self.0 ^= self.0 << 17;
+                self.0
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        let nonce = RngUuidNonce::new(XorShift(0x2545_f491_4f6c_dd1d), UuidFormat::Hyphenated);
+        let first = nonce.generate();
+        assert_eq!(first, "e7e3a8ea-0b28-4c7f-a0ab-f91c871971e4");
+        assert_eq!(Uuid::parse_str(&first).unwrap().get_version(), Some(uuid::Version::Random));
+        assert_ne!(nonce.generate(), first);
+    }
+
+    #[test]
+    fn test_counter_nonce() {
+        let s = Signer::new(Credential::new("ak", "sk"), "vm", "cn-north-1")
+            .with_nonce_generator(CounterNonce::default());
+        let mut nonces = Vec::new();
+        for _ in 0..2 {
+            let mut req = http::Request::builder()
+                .uri("https://vm.jdcloud-api.com/v1/instances")
+                .body(String::new())
+                .unwrap();
+            s.sign_request(&mut req).unwrap();
+            nonces.push(req.headers()["x-jdcloud-nonce"].to_str().unwrap().to_string());
+        }
+        assert_eq!(nonces, ["00000000000000000001", "00000000000000000002"]);
+
+        let nonce = CounterNonce::new(123).with_width(2);
+        assert_eq!(nonce.generate(), "123");
+        assert_eq!(CounterNonce::new(7).with_width(4).generate(), "0007");
+    }
+}
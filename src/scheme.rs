@@ -0,0 +1,300 @@
+use std::io::{self, Read};
+
+use crypto::digest::Digest;
+use crypto::hmac::Hmac;
+use crypto::mac::Mac;
+use crypto::sha2::{Sha256, Sha512};
+
+use crate::signer::{base16, LONG_DATE_FORMAT_STR};
+
+static JDCLOUD2_HMAC_SHA256: &str = "JDCLOUD2-HMAC-SHA256";
+
+/// The hash function of a signing scheme. It hashes the payload and the
+/// canonical request and drives the HMAC chain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DigestAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    pub(crate) fn hash(self, data: &[u8]) -> String {
+        match self {
+            DigestAlgorithm::Sha256 => hash(Sha256::new(), data),
+            DigestAlgorithm::Sha512 => hash(Sha512::new(), data),
+        }
+    }
+
+    /// The length of a hash in lowercase hex.
+    pub(crate) fn hex_len(self) -> usize {
+        match self {
+            DigestAlgorithm::Sha256 => 64,
+            DigestAlgorithm::Sha512 => 128,
+        }
+    }
+
+    /// The RFC 3230 `Digest` header value, e.g. `SHA-256=<base64>`, of the
+    /// body `payload_hash` is the hex hash of, the base64 being of the raw
+    /// hash bytes. `None` if `payload_hash` isn't such a hash, as for
+    /// `UNSIGNED-PAYLOAD`.
+    pub(crate) fn digest_header(self, payload_hash: &str) -> Option<String> {
+        if payload_hash.len() != self.hex_len() {
+            return None
+        }
+        let raw = (0..payload_hash.len()).step_by(2)
+            .map(|i| u8::from_str_radix(payload_hash.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let name = match self {
+            DigestAlgorithm::Sha256 => "SHA-256",
+            DigestAlgorithm::Sha512 => "SHA-512",
+        };
+        Some(format!("{}={}", name, base64(&raw)))
+    }
+
+    /// Hashes everything `reader` yields, a chunk at a time.
+    pub(crate) fn hash_reader<R: Read>(self, reader: R) -> io::Result<String> {
+        match self {
+            DigestAlgorithm::Sha256 => hash_reader(Sha256::new(), reader),
+            DigestAlgorithm::Sha512 => hash_reader(Sha512::new(), reader),
+        }
+    }
+
+    pub(crate) fn hmac(self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        match self {
+            DigestAlgorithm::Sha256 => hmac(Hmac::new(Sha256::new(), key), data),
+            DigestAlgorithm::Sha512 => hmac(Hmac::new(Sha512::new(), key), data),
+        }
+    }
+}
+
+fn hash<D: Digest>(mut digest: D, data: &[u8]) -> String {
+    digest.input(data);
+    digest.result_str()
+}
+
+fn hash_reader<D: Digest, R: Read>(mut digest: D, mut reader: R) -> io::Result<String> {
+    let mut buf = [0u8; 8192];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return Ok(digest.result_str()),
+            Ok(n) => digest.input(&buf[..n]),
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn hmac<M: Mac>(mut mac: M, data: &[u8]) -> Vec<u8> {
+    mac.input(data);
+    mac.result().code().to_vec()
+}
+
+/// How the signature bytes are written in `Authorization` and presigned URLs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignatureEncoding {
+    /// Lowercase hex, the default.
+    #[default]
+    Base16,
+    /// Standard base64 with padding, as used by some forks.
+    Base64,
+}
+
+impl SignatureEncoding {
+    pub(crate) fn encode(self, data: &[u8]) -> String {
+        match self {
+            SignatureEncoding::Base16 => base16(data),
+            SignatureEncoding::Base64 => base64(data),
+        }
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut res = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                res.push(ALPHABET[n >> (18 - 6 * i) & 63] as char);
+            } else {
+                res.push('=');
+            }
+        }
+    }
+    res
+}
+
+/// The algorithm-dependent parts of the signature: the algorithm token that
+/// goes into the string to sign and `Authorization`, its hash function, and
+/// the format of `x-jdcloud-date`, and the separators of the credential scope
+/// and of the lines of the canonical request and string to sign.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SigningScheme {
+    algorithm: String,
+    digest: DigestAlgorithm,
+    date_format: String,
+    lowercase_authorization_algorithm: bool,
+    scope_separator: char,
+    signature_encoding: SignatureEncoding,
+    signed_headers_separator: char,
+    line_separator: String,
+}
+
+impl SigningScheme {
+    pub fn new<S>(algorithm: S, digest: DigestAlgorithm) -> SigningScheme
+        where S: Into<String>
+    {
+        SigningScheme {
+            algorithm: algorithm.into(),
+            digest,
+            date_format: LONG_DATE_FORMAT_STR.to_string(),
+            lowercase_authorization_algorithm: false,
+            scope_separator: '/',
+            signature_encoding: SignatureEncoding::Base16,
+            signed_headers_separator: ';',
+            line_separator: "\n".to_string(),
+        }
+    }
+
+    /// `strftime` format of `x-jdcloud-date` and the date in the string to
+    /// sign, `%Y%m%dT%H%M%SZ` by default. The credential scope always uses
+    /// `%Y%m%d`.
+    pub fn with_date_format<S>(mut self, format: S) -> SigningScheme
+        where S: Into<String>
+    {
+        self.date_format = format.into();
+        self
+    }
+
+    /// Write the algorithm token lowercased in `Authorization`, e.g.
+    /// `jdcloud2-hmac-sha256`. The string to sign keeps it as given.
+    pub fn with_lowercase_authorization_algorithm(mut self, lowercase: bool) -> SigningScheme {
+        self.lowercase_authorization_algorithm = lowercase;
+        self
+    }
+
+    /// The character joining date, region, service and terminator in the
+    /// credential scope, `/` by default. The access key is still separated
+    /// from the scope with `/` in `Authorization`.
+    pub fn with_scope_separator(mut self, separator: char) -> SigningScheme {
+        self.scope_separator = separator;
+        self
+    }
+
+    /// The character joining the names in `SignedHeaders`, both in the
+    /// canonical request and in `Authorization`, `;` by default.
+    pub fn with_signed_headers_separator(mut self, separator: char) -> SigningScheme {
+        self.signed_headers_separator = separator;
+        self
+    }
+
+    /// The string ending each canonical header and joining the lines of the
+    /// canonical request and string to sign, `\n` by default. A forked
+    /// scheme uses `\r\n`.
+    pub fn with_line_separator<S>(mut self, separator: S) -> SigningScheme
+        where S: Into<String>
+    {
+        self.line_separator = separator.into();
+        self
+    }
+
+    /// Write signatures as `encoding` instead of hex. `Signer::sign_bytes`
+    /// always returns hex.
+    pub fn with_signature_encoding(mut self, encoding: SignatureEncoding) -> SigningScheme {
+        self.signature_encoding = encoding;
+        self
+    }
+
+    /// `JDCLOUD2-HMAC-SHA256`, the scheme JD Cloud uses today.
+    pub fn jdcloud2() -> SigningScheme {
+        SigningScheme::new(JDCLOUD2_HMAC_SHA256, DigestAlgorithm::Sha256)
+    }
+
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    pub fn digest(&self) -> DigestAlgorithm {
+        self.digest
+    }
+
+    pub fn date_format(&self) -> &str {
+        &self.date_format
+    }
+
+    pub fn scope_separator(&self) -> char {
+        self.scope_separator
+    }
+
+    pub fn signed_headers_separator(&self) -> char {
+        self.signed_headers_separator
+    }
+
+    pub fn line_separator(&self) -> &str {
+        &self.line_separator
+    }
+
+    pub fn signature_encoding(&self) -> SignatureEncoding {
+        self.signature_encoding
+    }
+
+    /// The algorithm token as written in `Authorization`.
+    pub fn authorization_algorithm(&self) -> String {
+        if self.lowercase_authorization_algorithm {
+            self.algorithm.to_lowercase()
+        } else {
+            self.algorithm.clone()
+        }
+    }
+}
+
+impl Default for SigningScheme {
+    fn default() -> SigningScheme {
+        SigningScheme::jdcloud2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(DigestAlgorithm::Sha256.hash(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(DigestAlgorithm::Sha512.hash(b""),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e");
+    }
+
+    #[test]
+    fn test_default() {
+        let scheme = SigningScheme::default();
+        assert_eq!(scheme.algorithm(), "JDCLOUD2-HMAC-SHA256");
+        assert_eq!(scheme.digest(), DigestAlgorithm::Sha256);
+        assert_eq!(scheme.date_format(), "%Y%m%dT%H%M%SZ");
+        assert_eq!(scheme.authorization_algorithm(), "JDCLOUD2-HMAC-SHA256");
+        let scheme = scheme.with_lowercase_authorization_algorithm(true);
+        assert_eq!(scheme.algorithm(), "JDCLOUD2-HMAC-SHA256");
+        assert_eq!(scheme.authorization_algorithm(), "jdcloud2-hmac-sha256");
+    }
+
+    #[test]
+    fn test_signature_encoding() {
+        assert_eq!(SignatureEncoding::Base16.encode(&[0x00, 0xab, 0xff]), "00abff");
+        let cases: Vec<(&[u8], &str)> = vec![
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (&[0xfb, 0xff], "+/8="),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(SignatureEncoding::Base64.encode(data), expected);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use http::Request;
+use http::request::Builder;
+
+use crate::error::Error;
+use crate::signer::Signer;
+
+/// Signs a request in the same expression that builds it.
+///
+/// ```
+/// use jdcloud_signer::{Credential, SignExt, Signer};
+/// use http::Request;
+///
+/// let signer = Signer::new(Credential::new("ak", "sk"), "vm", "cn-north-1");
+/// let req = Request::builder()
+///     .method("GET")
+///     .uri("https://vm.jdcloud-api.com/v1/regions/cn-north-1/instances")
+///     .sign_with(&signer)
+///     .unwrap();
+/// assert!(req.headers().contains_key("authorization"));
+/// ```
+pub trait SignExt {
+    fn sign_with(self, signer: &Signer) -> Result<Request<String>, Error>;
+}
+
+impl SignExt for Request<String> {
+    fn sign_with(mut self, signer: &Signer) -> Result<Request<String>, Error> {
+        signer.sign_request(&mut self)?;
+        Ok(self)
+    }
+}
+
+/// Finishes the builder with an empty body.
+impl SignExt for Builder {
+    fn sign_with(self, signer: &Signer) -> Result<Request<String>, Error> {
+        self.body(String::new())
+            .map_err(Error::new_invalid_request)?
+            .sign_with(signer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credential::Credential;
+
+    #[test]
+    fn test_sign_with() {
+        let s = Signer::new(Credential::new("ak", "sk"), "service_name", "cn-north-1");
+        let req = Request::builder()
+            .uri("https://www.jdcloud-api.com/v1/regions/cn-north-1/instances")
+            .body("a".to_string())
+            .unwrap()
+            .sign_with(&s)
+            .unwrap();
+        assert!(s.verify_request(&req).unwrap());
+
+        let err = Request::builder().uri("not a uri").sign_with(&s).unwrap_err();
+        assert!(err.is_invalid_request());
+
+        let s = Signer::new(Credential::new("", ""), "service_name", "cn-north-1");
+        assert!(Request::builder().sign_with(&s).unwrap_err().is_invalid_credential());
+    }
+}
@@ -0,0 +1,57 @@
+use std::ops::Deref;
+
+use http::Request;
+
+use crate::error::Error;
+use crate::signer::Signer;
+
+/// A value that has been through `Signer::sign_signed`, so functions that
+/// send requests can take `Signed<Request<_>>` and refuse unsigned ones at
+/// compile time. It can only be built by signing.
+#[derive(Debug)]
+pub struct Signed<T>(T);
+
+impl<T> Signed<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Signed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl Signer {
+    /// Signs `request` like `sign_request` and wraps it in `Signed`.
+    pub fn sign_signed<B>(&self, mut request: Request<B>) -> Result<Signed<Request<B>>, Error>
+        where B: AsRef<[u8]>
+    {
+        self.sign_request(&mut request)?;
+        Ok(Signed(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::AUTHORIZATION;
+    use crate::credential::Credential;
+    use crate::signer::tests::make_test_request;
+
+    #[test]
+    fn test_sign_signed() {
+        let s = Signer::new(Credential::new("ak", "sk"), "service_name", "cn-north-1");
+        let signed: Signed<Request<String>> = s.sign_signed(make_test_request()).unwrap();
+        assert!(signed.headers().contains_key(AUTHORIZATION));
+        let req = signed.into_inner();
+        assert!(req.headers().contains_key(AUTHORIZATION));
+        assert!(s.verify_request(&req).unwrap());
+
+        let s = Signer::new(Credential::new("", ""), "service_name", "cn-north-1");
+        assert!(s.sign_signed(make_test_request()).unwrap_err().is_invalid_credential());
+    }
+}
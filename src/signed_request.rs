@@ -0,0 +1,135 @@
+use chrono::prelude::*;
+use http::Request;
+use http::header::AUTHORIZATION;
+
+use crate::error::Error;
+use crate::scheme::SigningScheme;
+use crate::signer::{DATE_HEADER, NONCE_HEADER};
+
+/// The signing-relevant fields of a request signed by `Signer`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedRequest {
+    pub date: DateTime<Utc>,
+    pub nonce: Option<String>,
+    pub algorithm: String,
+    pub access_key: String,
+    pub credential_scope: String,
+    pub signed_headers: Vec<String>,
+    pub signature: String,
+}
+
+impl SignedRequest {
+    /// Reads the `x-jdcloud-date`, `x-jdcloud-nonce` and `Authorization`
+    /// headers of `request`. Nothing is verified.
+    pub fn from_request<B>(request: &Request<B>) -> Result<SignedRequest, Error> {
+        SignedRequest::from_request_with_scheme(request, &SigningScheme::default())
+    }
+
+    pub(crate) fn from_request_with_scheme<B>(request: &Request<B>, scheme: &SigningScheme) -> Result<SignedRequest, Error> {
+        let authorization = match request.headers().get(AUTHORIZATION) {
+            Some(v) => v.to_str().map_err(|_| Error::new_malformed_authorization())?,
+            None => return Err(Error::new_malformed_authorization()),
+        };
+        let mut signed_request = parse_authorization(authorization, scheme.signed_headers_separator())?;
+        let date = match request.headers().get(DATE_HEADER) {
+            Some(v) => v.to_str().map_err(Error::new_malformed_date)?,
+            None => return Err(Error::new_malformed_date("missing x-jdcloud-date header")),
+        };
+        let nonce = match request.headers().get(NONCE_HEADER) {
+            Some(v) => Some(v.to_str().map_err(|_| Error::new_malformed_authorization())?.to_string()),
+            None => None,
+        };
+
+        signed_request.date = parse_date(date, scheme.date_format())?;
+        signed_request.nonce = nonce;
+        Ok(signed_request)
+    }
+}
+
+pub(crate) fn parse_date(date: &str, date_format: &str) -> Result<DateTime<Utc>, Error> {
+    let date = NaiveDateTime::parse_from_str(date, date_format)
+        .map_err(Error::new_malformed_date)?;
+    Ok(DateTime::from_utc(date, Utc))
+}
+
+fn parse_authorization(value: &str, signed_headers_separator: char) -> Result<SignedRequest, Error> {
+    let mut it = value.splitn(2, ' ');
+    let algorithm = it.next().unwrap_or_default();
+    let mut credential = None;
+    let mut signed_headers = None;
+    let mut signature = None;
+    for field in it.next().unwrap_or_default().split(", ") {
+        let mut kv = field.splitn(2, '=');
+        match (kv.next(), kv.next()) {
+            (Some("Credential"), Some(v)) => credential = Some(v),
+            (Some("SignedHeaders"), Some(v)) => signed_headers = Some(v),
+            (Some("Signature"), Some(v)) => signature = Some(v),
+            _ => return Err(Error::new_malformed_authorization()),
+        }
+    }
+    let mut credential = match credential {
+        Some(c) => c.splitn(2, '/'),
+        None => return Err(Error::new_malformed_authorization()),
+    };
+    match (credential.next(), credential.next(), signed_headers, signature) {
+        (Some(ak), Some(credential_scope), Some(signed_headers), Some(signature)) => Ok(SignedRequest {
+            date: Utc.timestamp(0, 0),
+            nonce: None,
+            algorithm: algorithm.to_string(),
+            access_key: ak.to_string(),
+            credential_scope: credential_scope.to_string(),
+            signed_headers: signed_headers.split(signed_headers_separator).map(|x| x.to_string()).collect(),
+            signature: signature.to_string(),
+        }),
+        _ => Err(Error::new_malformed_authorization()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Credential, Signer};
+    use crate::signer::LONG_DATE_FORMAT_STR;
+    use http::header::HeaderValue;
+
+    #[test]
+    fn test_from_request() {
+        let s = Signer::new(Credential::new("ak", "sk"), "service_name", "cn-north-1");
+        let mut req = Request::builder()
+            .uri("https://www.jdcloud-api.com/v1/regions/cn-north-1/instances?pageNumber=2&pageSize=10")
+            .method("GET")
+            .header("content-type", "application/json")
+            .body("".to_string())
+            .unwrap();
+        s.sign_request(&mut req).unwrap();
+
+        let signed = SignedRequest::from_request(&req).unwrap();
+        assert_eq!(signed.date.format(LONG_DATE_FORMAT_STR).to_string(), req.headers().get(DATE_HEADER).unwrap().to_str().unwrap());
+        assert_eq!(signed.nonce.as_deref(), Some(req.headers().get(NONCE_HEADER).unwrap().to_str().unwrap()));
+        assert_eq!(signed.algorithm, "JDCLOUD2-HMAC-SHA256");
+        assert_eq!(signed.access_key, "ak");
+        assert_eq!(signed.credential_scope, format!("{}/cn-north-1/service_name/jdcloud2_request", signed.date.format("%Y%m%d")));
+        assert_eq!(signed.signed_headers, ["content-type", "x-jdcloud-date", "x-jdcloud-nonce"]);
+        assert_eq!(signed.signature.len(), 64);
+        assert!(req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap()
+            .ends_with(&format!("Signature={}", signed.signature)));
+    }
+
+    #[test]
+    fn test_from_request_malformed() {
+        let mut req = Request::builder().body("".to_string()).unwrap();
+        assert!(SignedRequest::from_request(&req).unwrap_err().is_malformed_authorization());
+
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_static("JDCLOUD2-HMAC-SHA256 Signature=abc"));
+        req.headers_mut().insert(DATE_HEADER, HeaderValue::from_static("20180405T010203Z"));
+        assert!(SignedRequest::from_request(&req).unwrap_err().is_malformed_authorization());
+
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_static("JDCLOUD2-HMAC-SHA256 Credential=ak/20180405/cn-north-1/service_name/jdcloud2_request, SignedHeaders=content-type;x-jdcloud-date;x-jdcloud-nonce, Signature=cea138630c57ba3de51933926e3b1657c34b4d1b6f86d360511bf40a09f63729"));
+        let signed = SignedRequest::from_request(&req).unwrap();
+        assert_eq!(signed.date, Utc.ymd(2018, 4, 5).and_hms(1, 2, 3));
+        assert_eq!(signed.nonce, None);
+
+        req.headers_mut().insert(DATE_HEADER, HeaderValue::from_static("2018-04-05"));
+        assert!(SignedRequest::from_request(&req).unwrap_err().is_malformed_date());
+    }
+}
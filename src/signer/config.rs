@@ -0,0 +1,257 @@
+use chrono::{DateTime, Duration, Utc};
+use http::header::{HeaderName, HeaderValue};
+#[cfg(feature="serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::compat::JdCloudSdkCompat;
+use crate::credential::Credential;
+use crate::scheme::SigningScheme;
+use super::{Signer, SigningMode, EXCLUDED_HEADERS, HOP_BY_HOP_HEADERS};
+
+/// The options a `Signer` was built with, without its credential, for
+/// pasting into bug reports next to a failing signature or, with the `serde`
+/// feature, for keeping in a config file and passing to `from_config`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct SignerConfig {
+    pub service_name: String,
+    pub region: String,
+    pub scheme: SigningScheme,
+    pub mode: SigningMode,
+    pub compat: JdCloudSdkCompat,
+    #[cfg_attr(feature="serde", serde(with = "seconds"))]
+    pub signature_reuse: Option<Duration>,
+    pub signing_host: Option<String>,
+    pub host_pattern: Option<String>,
+    pub strict_lowercase_headers: bool,
+    pub manage_user_agent: bool,
+    pub decode_then_encode_path: bool,
+    pub rfc3986_query: bool,
+    pub omit_nonce: bool,
+    pub set_date_header: bool,
+    pub require_host: bool,
+    pub reject_duplicate_query_keys: bool,
+    pub strip_default_host_port: bool,
+    pub max_signed_headers_bytes: Option<usize>,
+    pub insertion_order_headers: bool,
+    #[cfg_attr(feature="serde", serde(with = "timestamp"))]
+    pub min_valid_date: Option<DateTime<Utc>>,
+    pub sign_hop_by_hop_headers: bool,
+    pub browser_safe_headers: bool,
+    pub self_check: bool,
+    pub account_pin: Option<String>,
+    pub add_digest_header: bool,
+    #[cfg_attr(feature="serde", serde(with = "seconds"))]
+    pub presign_expires_in: Option<Duration>,
+    /// Header names that are never signed. `from_config` ignores it.
+    pub excluded_headers: Vec<String>,
+    /// Headers inserted before signing when the request lacks them, as
+    /// `(name, value)`. `from_config` skips entries that aren't valid headers.
+    pub default_headers: Vec<(String, String)>,
+    /// Headers signed in presigned URLs besides `host`. `from_config` skips
+    /// names that aren't valid headers.
+    pub presign_signed_headers: Vec<String>,
+}
+
+impl Signer {
+    pub fn config_snapshot(&self) -> SignerConfig {
+        SignerConfig {
+            service_name: self.service_name.clone(),
+            region: self.region.clone(),
+            scheme: self.scheme.clone(),
+            mode: self.mode,
+            compat: self.compat,
+            signature_reuse: self.signature_reuse,
+            signing_host: self.signing_host.clone(),
+            host_pattern: self.host_pattern.clone(),
+            strict_lowercase_headers: self.strict_lowercase_headers,
+            manage_user_agent: self.manage_user_agent,
+            decode_then_encode_path: self.decode_then_encode_path,
+            rfc3986_query: self.rfc3986_query,
+            omit_nonce: self.omit_nonce,
+            set_date_header: self.set_date_header,
+            require_host: self.require_host,
+            reject_duplicate_query_keys: self.reject_duplicate_query_keys,
+            strip_default_host_port: self.strip_default_host_port,
+            max_signed_headers_bytes: self.max_signed_headers_bytes,
+            insertion_order_headers: self.insertion_order_headers,
+            min_valid_date: self.min_valid_date,
+            sign_hop_by_hop_headers: self.sign_hop_by_hop_headers,
+            browser_safe_headers: self.browser_safe_headers,
+            self_check: self.self_check,
+            account_pin: self.account_pin.clone(),
+            add_digest_header: self.add_digest_header,
+            presign_expires_in: self.presign_expires_in,
+            excluded_headers: EXCLUDED_HEADERS.iter()
+                .chain(HOP_BY_HOP_HEADERS.iter().filter(|_| !self.sign_hop_by_hop_headers))
+                .map(|x| x.to_string())
+                .collect(),
+            default_headers: self.default_headers.iter()
+                .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+                .collect(),
+            presign_signed_headers: self.presign_signed_headers.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    /// Rebuilds a signer from `config_snapshot`, with `credential` supplied
+    /// separately so it never has to live in the config. The nonce generator
+    /// is the default one, and no header value transform is set.
+    pub fn from_config(config: SignerConfig, credential: Credential) -> Signer {
+        let mut signer = Signer::new(credential, config.service_name, config.region);
+        signer.scheme = config.scheme;
+        signer.mode = config.mode;
+        signer.compat = config.compat;
+        signer.signature_reuse = config.signature_reuse;
+        signer.signing_host = config.signing_host;
+        signer.host_pattern = config.host_pattern;
+        signer.strict_lowercase_headers = config.strict_lowercase_headers;
+        signer.manage_user_agent = config.manage_user_agent;
+        signer.decode_then_encode_path = config.decode_then_encode_path;
+        signer.rfc3986_query = config.rfc3986_query;
+        signer.omit_nonce = config.omit_nonce;
+        signer.set_date_header = config.set_date_header;
+        signer.require_host = config.require_host;
+        signer.reject_duplicate_query_keys = config.reject_duplicate_query_keys;
+        signer.strip_default_host_port = config.strip_default_host_port;
+        signer.max_signed_headers_bytes = config.max_signed_headers_bytes;
+        signer.insertion_order_headers = config.insertion_order_headers;
+        signer.min_valid_date = config.min_valid_date;
+        signer.sign_hop_by_hop_headers = config.sign_hop_by_hop_headers;
+        signer.browser_safe_headers = config.browser_safe_headers;
+        signer.self_check = config.self_check;
+        signer.account_pin = config.account_pin;
+        signer.add_digest_header = config.add_digest_header;
+        signer.presign_expires_in = config.presign_expires_in;
+        for (name, value) in config.default_headers {
+            if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(&value)) {
+                signer.default_headers.insert(name, value);
+            }
+        }
+        for name in config.presign_signed_headers {
+            if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+                signer = signer.with_presign_signed_header(name);
+            }
+        }
+        signer
+    }
+}
+
+/// `Option<Duration>` as a whole number of seconds.
+#[cfg(feature="serde")]
+mod seconds {
+    use chrono::Duration;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(duration) => serializer.serialize_some(&duration.num_seconds()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+        // `Duration::seconds` panics past `i64::MAX` milliseconds.
+        match Option::<i64>::deserialize(deserializer)? {
+            Some(x) if !(-i64::MAX / 1000..=i64::MAX / 1000).contains(&x) => Err(D::Error::custom(format!("duration out of range: {}s", x))),
+            x => Ok(x.map(Duration::seconds)),
+        }
+    }
+}
+
+/// `Option<DateTime<Utc>>` as a Unix timestamp in seconds.
+#[cfg(feature="serde")]
+mod timestamp {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(date) => serializer.serialize_some(&date.timestamp()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> {
+        match Option::<i64>::deserialize(deserializer)? {
+            Some(x) => Utc.timestamp_opt(x, 0).single()
+                .map(Some)
+                .ok_or_else(|| D::Error::custom(format!("timestamp out of range: {}", x))),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_snapshot() {
+        let s = Signer::new(Credential::new("ak", "sk"), "vm", "cn-north-1");
+        let config = s.config_snapshot();
+        assert_eq!(config.service_name, "vm");
+        assert!(!config.decode_then_encode_path);
+        assert_eq!(config.compat, JdCloudSdkCompat::Spec);
+        assert_eq!(&config.excluded_headers[..3], ["authorization", "user-agent", "connection"]);
+        assert!(config.excluded_headers.iter().any(|x| x == "proxy-authorization"));
+
+        let s = s.with_decode_then_encode_path(true).with_compat(JdCloudSdkCompat::JavaSdk);
+        let config = s.config_snapshot();
+        assert!(config.decode_then_encode_path);
+        assert_eq!(config.compat, JdCloudSdkCompat::JavaSdk);
+        assert!(!format!("{:?}", config).contains("sk"));
+
+        let config = s.with_sign_hop_by_hop_headers(true).config_snapshot();
+        assert_eq!(config.excluded_headers, ["authorization", "user-agent"]);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let s = Signer::new(Credential::new("ak", "sk"), "vm", "cn-north-1")
+            .with_signature_reuse(Duration::minutes(5))
+            .with_rfc3986_query(true)
+            .with_default_header(http::header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let rebuilt = Signer::from_config(s.config_snapshot(), Credential::new("ak", "sk"));
+        assert_eq!(rebuilt.config_snapshot(), s.config_snapshot());
+    }
+
+    #[cfg(feature="serde")]
+    #[test]
+    fn test_config_json_roundtrip() {
+        use chrono::prelude::*;
+        use crate::signer::tests::make_test_request;
+        use http::header::AUTHORIZATION;
+
+        let s = Signer::new(Credential::new("ak", "sk"), "vm", "cn-north-1")
+            .with_signing_scheme(SigningScheme::jdcloud2().with_lowercase_authorization_algorithm(true))
+            .with_compat(JdCloudSdkCompat::GoSdk)
+            .with_presign_expires_in(Duration::hours(1))
+            .with_min_valid_date(Utc.ymd(2018, 1, 1).and_hms(0, 0, 0));
+        let json = serde_json::to_string(&s.config_snapshot()).unwrap();
+        assert!(!json.contains("sk"));
+        let config: SignerConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config, s.config_snapshot());
+
+        let rebuilt = Signer::from_config(config, Credential::new("ak", "sk"));
+        let now = Utc.ymd(2018, 4, 5).and_hms(1, 2, 3);
+        let mut expected = make_test_request();
+        let mut req = make_test_request();
+        s.sign_request_2(&mut expected, &now, "nonce").unwrap();
+        rebuilt.sign_request_2(&mut req, &now, "nonce").unwrap();
+        assert_eq!(req.headers()[AUTHORIZATION], expected.headers()[AUTHORIZATION]);
+    }
+
+    #[cfg(feature="serde")]
+    #[test]
+    fn test_config_json_out_of_range() {
+        let mut json = serde_json::to_value(Signer::new(Credential::new("ak", "sk"), "vm", "cn-north-1").config_snapshot()).unwrap();
+        json["signature_reuse"] = i64::MAX.into();
+        assert!(serde_json::from_value::<SignerConfig>(json.clone()).is_err());
+
+        json["signature_reuse"] = serde_json::Value::Null;
+        json["min_valid_date"] = i64::MAX.into();
+        assert!(serde_json::from_value::<SignerConfig>(json).is_err());
+    }
+}
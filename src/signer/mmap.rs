@@ -0,0 +1,76 @@
+use std::fs::File;
+use std::path::Path;
+
+use chrono::prelude::*;
+use http::Request;
+use http::header::HeaderMap;
+use memmap2::Mmap;
+
+use crate::error::Error;
+
+use super::Signer;
+
+impl Signer {
+    /// Signs `head`, whose body is the file at `path`, hashing the file
+    /// through a memory map instead of reading it onto the heap. The signing
+    /// headers are inserted into `head`; send the file as the body.
+    ///
+    /// The file must not change, be truncated or be replaced while it is
+    /// hashed or before it is sent: a change while mapped is undefined
+    /// behavior, and any change after hashing makes the signature wrong.
+    pub fn sign_request_with_mmap(&self, head: &mut Request<()>, path: &Path) -> Result<(), Error> {
+        if !self.credential.is_valid() {
+            return Err(Error::new_invalid_credential())
+        }
+
+        let now: DateTime<Utc> = Utc::now();
+        let uuid = self.nonce_generator.generate();
+        self.sign_request_with_mmap_2(head, path, &now, &uuid)
+    }
+
+    fn sign_request_with_mmap_2(&self, head: &mut Request<()>, path: &Path, now: &DateTime<Utc>,
+                                uuid: &str) -> Result<(), Error> {
+        let file = File::open(path)?;
+        // Mapping an empty file fails on some platforms.
+        let payload_hash = if file.metadata()?.len() == 0 {
+            self.scheme.digest().hash(b"")
+        } else {
+            // Safety: the caller promises the file isn't modified while mapped.
+            let map = unsafe { Mmap::map(&file)? };
+            self.scheme.digest().hash(&map)
+        };
+        let headers = self.sign_request_with_overlay_and_hash(head, &HeaderMap::new(), now, uuid, &payload_hash)?;
+        head.headers_mut().extend(headers);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::credential::Credential;
+    use crate::signer::tests::make_test_request;
+    use http::header::AUTHORIZATION;
+
+    #[test]
+    fn test_sign_request_with_mmap() {
+        let body: Vec<u8> = (0..100_000u32).map(|x| (x % 251) as u8).collect();
+        let path = std::env::temp_dir().join(format!("jdcloud-signer-mmap-{}", std::process::id()));
+        fs::write(&path, &body).unwrap();
+
+        let s = Signer::new(Credential::new("ak", "sk"), "service_name", "cn-north-1");
+        let now = Utc.ymd(2018, 4, 5).and_hms(1, 2, 3);
+        let (parts, _) = make_test_request().into_parts();
+        let mut head = Request::from_parts(parts, ());
+        let result = s.sign_request_with_mmap_2(&mut head, &path, &now, "nonce");
+        fs::remove_file(&path).unwrap();
+        result.unwrap();
+
+        let (parts, _) = make_test_request().into_parts();
+        let mut expected = Request::from_parts(parts, body);
+        s.sign_request_2(&mut expected, &now, "nonce").unwrap();
+        assert_eq!(head.headers()[AUTHORIZATION], expected.headers()[AUTHORIZATION]);
+    }
+}
@@ -0,0 +1,73 @@
+use std::iter;
+
+use chrono::prelude::*;
+
+use crate::credential::Credential;
+use super::{Signer, DATE_HEADER, NONCE_HEADER, make_canonical_request_str_from_parts};
+
+/// Every input of a signature, spelled out, for `Signer::compute_signature`.
+///
+/// `path` and `query` are taken as already canonical, and `headers` holds the
+/// signed headers other than `x-jdcloud-date` and `x-jdcloud-nonce`, which
+/// come from `date` and `nonce`.
+#[derive(Clone, Debug)]
+pub struct SignatureParams<'a> {
+    pub access_key: &'a str,
+    pub secret_key: &'a str,
+    pub region: &'a str,
+    pub service_name: &'a str,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub query: &'a str,
+    pub headers: &'a [(&'a str, &'a str)],
+    pub payload_hash: &'a str,
+    pub date: DateTime<Utc>,
+    pub nonce: &'a str,
+}
+
+impl Signer {
+    /// The hex signature for `params` with the default signing scheme,
+    /// without building an `http::Request`, for conformance tests of the
+    /// crypto on its own.
+    pub fn compute_signature(params: SignatureParams<'_>) -> String {
+        let signer = Signer::new(Credential::new(params.access_key, params.secret_key),
+                                 params.service_name, params.region);
+        let date = params.date.format(signer.scheme.date_format()).to_string();
+        let headers = params.headers.iter()
+            .filter(|x| !x.0.eq_ignore_ascii_case(DATE_HEADER) && !x.0.eq_ignore_ascii_case(NONCE_HEADER))
+            .copied()
+            .chain(iter::once((DATE_HEADER, date.as_str())))
+            .chain(iter::once((NONCE_HEADER, params.nonce)));
+        let (headers, signed_headers) = signer.make_canonical_header_str_from(headers)
+            .expect("default signer accepts any header names");
+        let canonical_request = make_canonical_request_str_from_parts(signer.scheme.line_separator(),
+            params.method, params.path, params.query, &headers, &signed_headers, params.payload_hash);
+        let scope = signer.scope();
+        let string_to_sign = signer.make_string_to_sign_from_canonical(&canonical_request, &params.date, &scope);
+        signer.make_signature(&string_to_sign, &params.date, &scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::EMPTY_STRING_SHA256;
+
+    #[test]
+    fn test_compute_signature() {
+        let signature = Signer::compute_signature(SignatureParams {
+            access_key: "ak",
+            secret_key: "sk",
+            region: "cn-north-1",
+            service_name: "service_name",
+            method: "GET",
+            path: "/v1/regions/cn-north-1/instances",
+            query: "pageNumber=2&pageSize=10",
+            headers: &[("content-type", "application/json")],
+            payload_hash: EMPTY_STRING_SHA256,
+            date: Utc.ymd(2018, 4, 5).and_hms(1, 2, 3),
+            nonce: "55f3919e-3a7d-4174-b117-f150ff25e274",
+        });
+        assert_eq!(signature, "cea138630c57ba3de51933926e3b1657c34b4d1b6f86d360511bf40a09f63729");
+    }
+}
@@ -0,0 +1,270 @@
+use std::io::Read;
+
+use chrono::prelude::*;
+use chrono::Duration;
+use crypto::util::fixed_time_eq;
+use http::Request;
+use http::header::AUTHORIZATION;
+
+use crate::error::Error;
+use crate::signed_request::{parse_date, SignedRequest};
+
+use super::{Signer, compute_payload_hash, DATE_HEADER, NONCE_HEADER};
+
+impl Signer {
+    /// Recomputes the signature of a request signed by `sign_request` and
+    /// compares it with the one carried in its `Authorization` header.
+    ///
+    /// Only the headers listed in `SignedHeaders` take part, so headers added
+    /// after signing don't invalidate the request. `user-agent` is never
+    /// signed, so its value, whoever set it, has no bearing on the result and
+    /// can't be trusted. The age of `x-jdcloud-date` is not checked.
+    pub fn verify_request<B>(&self, request: &Request<B>) -> Result<bool, Error>
+        where B: AsRef<[u8]>
+    {
+        self.verify_request_with_hash(request, &compute_payload_hash(request, self.scheme.digest()))
+    }
+
+    /// Like `verify_request`, for a body too large to buffer: `head` carries
+    /// the method, URI and headers, and `body` is hashed as it is read.
+    pub fn verify_request_streaming<R>(&self, head: &Request<()>, body: R) -> Result<bool, Error>
+        where R: Read
+    {
+        let payload_hash = self.scheme.digest().hash_reader(body)?;
+        self.verify_request_with_hash(head, &payload_hash)
+    }
+
+    fn verify_request_with_hash<B>(&self, request: &Request<B>, payload_hash: &str) -> Result<bool, Error> {
+        if !self.credential.is_valid() {
+            return Err(Error::new_invalid_credential())
+        }
+
+        let signed_request = SignedRequest::from_request_with_scheme(request, &self.scheme)?;
+        let now = signed_request.date;
+        let scope = self.scope_for(request.uri());
+        if signed_request.algorithm != self.scheme.authorization_algorithm()
+            || signed_request.access_key != self.credential.ak()
+            || signed_request.credential_scope != self.make_credential_scope(&now, &scope) {
+            return Ok(false)
+        }
+
+        let signed = &signed_request.signed_headers;
+        let (canonical_request, signed_headers) = self.make_canonical_request_str_with_hash(request,
+            self.header_strs(request.headers().iter()
+                .filter(|x| signed.iter().any(|name| x.0.as_str() == name)))?.into_iter(),
+            payload_hash)?;
+        if signed_headers != signed.join(self.scheme.signed_headers_separator().encode_utf8(&mut [0; 4])) {
+            return Ok(false)
+        }
+
+        let string_to_sign = self.make_string_to_sign_from_canonical(&canonical_request, &now, &scope);
+        let signature = self.make_signature(&string_to_sign, &now, &scope);
+        Ok(fixed_time_eq(signature.as_bytes(), signed_request.signature.as_bytes()))
+    }
+
+    /// A cheap check that `request` carries this signer's signing headers:
+    /// `x-jdcloud-date`, `x-jdcloud-nonce` (unless omitted) and an
+    /// `Authorization` starting with the algorithm. Nothing is verified.
+    pub fn is_signed<B>(&self, request: &Request<B>) -> bool {
+        let headers = request.headers();
+        let has_algorithm = headers.get(AUTHORIZATION)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.strip_prefix(self.scheme.authorization_algorithm().as_str()))
+            .is_some_and(|x| x.starts_with(' '));
+        has_algorithm && headers.contains_key(DATE_HEADER) && (self.omit_nonce || headers.contains_key(NONCE_HEADER))
+    }
+
+    /// When a request signed in headers goes stale: its `x-jdcloud-date`
+    /// plus the server's allowed clock skew `skew`, often 15 minutes. Re-sign
+    /// before then. Nothing else about the request is checked.
+    pub fn signed_request_expires_at<B>(&self, request: &Request<B>, skew: Duration) -> Result<DateTime<Utc>, Error> {
+        let date = match request.headers().get(DATE_HEADER) {
+            Some(v) => v.to_str().map_err(Error::new_malformed_date)?,
+            None => return Err(Error::new_malformed_date("missing x-jdcloud-date header")),
+        };
+        Ok(parse_date(date, self.scheme.date_format())? + skew)
+    }
+
+    pub(super) fn has_fresh_signature<B>(&self, request: &Request<B>, now: &DateTime<Utc>, max_age: Duration) -> bool
+        where B: AsRef<[u8]>
+    {
+        match (self.verify_request(request), SignedRequest::from_request_with_scheme(request, &self.scheme)) {
+            (Ok(true), Ok(signed)) => signed.date <= *now && *now - signed.date <= max_age,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credential::Credential;
+    use crate::signer::tests::make_test_request;
+    use http::header::{HeaderValue, CONTENT_TYPE, USER_AGENT};
+
+    fn make_signer() -> Signer {
+        Signer::new(Credential::new("ak", "sk"), "service_name", "cn-north-1")
+    }
+
+    #[test]
+    fn test_verify_request() {
+        let s = make_signer();
+        let mut req = make_test_request();
+        s.sign_request(&mut req).unwrap();
+        assert!(s.verify_request(&req).unwrap());
+
+        req.headers_mut().insert("x-added-later", HeaderValue::from_static("1"));
+        assert!(s.verify_request(&req).unwrap());
+
+        req.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        assert!(!s.verify_request(&req).unwrap());
+    }
+
+    #[test]
+    fn test_verify_request_other_credential() {
+        let mut req = make_test_request();
+        make_signer().sign_request(&mut req).unwrap();
+        let s = Signer::new(Credential::new("ak", "sk2"), "service_name", "cn-north-1");
+        assert!(!s.verify_request(&req).unwrap());
+        let s = Signer::new(Credential::new("ak", "sk"), "service_name", "cn-east-2");
+        assert!(!s.verify_request(&req).unwrap());
+    }
+
+    #[test]
+    fn test_verify_request_any_user_agent() {
+        let mut req = make_test_request();
+        req.headers_mut().insert(USER_AGENT, HeaderValue::from_static("foo/1.0"));
+        make_signer().with_manage_user_agent(false).sign_request(&mut req).unwrap();
+        assert!(make_signer().verify_request(&req).unwrap());
+
+        req.headers_mut().insert(USER_AGENT, HeaderValue::from_static("bar/2.0"));
+        assert!(make_signer().verify_request(&req).unwrap());
+        req.headers_mut().remove(USER_AGENT);
+        assert!(make_signer().with_manage_user_agent(false).verify_request(&req).unwrap());
+    }
+
+    #[test]
+    fn test_is_signed() {
+        let s = make_signer();
+        let mut req = make_test_request();
+        assert!(!s.is_signed(&req));
+        s.sign_request(&mut req).unwrap();
+        assert!(s.is_signed(&req));
+
+        req.headers_mut().remove(NONCE_HEADER);
+        assert!(!s.is_signed(&req));
+        assert!(make_signer().with_omit_nonce(true).is_signed(&req));
+
+        let mut req = make_test_request();
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_static("Bearer abc"));
+        req.headers_mut().insert(DATE_HEADER, HeaderValue::from_static("20180405T010203Z"));
+        req.headers_mut().insert(NONCE_HEADER, HeaderValue::from_static("nonce"));
+        assert!(!s.is_signed(&req));
+    }
+
+    #[test]
+    fn test_signed_request_expires_at() {
+        let s = make_signer();
+        let mut req = make_test_request();
+        let now = Utc.ymd(2018, 4, 5).and_hms(1, 2, 3);
+        s.sign_request_2(&mut req, &now, "nonce").unwrap();
+        assert_eq!(s.signed_request_expires_at(&req, Duration::minutes(15)).unwrap(),
+            Utc.ymd(2018, 4, 5).and_hms(1, 17, 3));
+
+        req.headers_mut().insert(DATE_HEADER, HeaderValue::from_static("2018-04-05T01:02:03Z"));
+        assert!(s.signed_request_expires_at(&req, Duration::minutes(15)).unwrap_err().is_malformed_date());
+        req.headers_mut().remove(DATE_HEADER);
+        assert!(s.signed_request_expires_at(&req, Duration::minutes(15)).unwrap_err().is_malformed_date());
+    }
+
+    #[test]
+    fn test_verify_request_malformed() {
+        let s = make_signer();
+        let mut req = make_test_request();
+        assert!(s.verify_request(&req).unwrap_err().is_malformed_authorization());
+
+        req.headers_mut().insert(AUTHORIZATION, HeaderValue::from_static("JDCLOUD2-HMAC-SHA256 Signature=abc"));
+        assert!(s.verify_request(&req).unwrap_err().is_malformed_authorization());
+
+        s.sign_request(&mut req).unwrap();
+        req.headers_mut().insert(DATE_HEADER, HeaderValue::from_static("2018-04-05"));
+        assert!(s.verify_request(&req).unwrap_err().is_malformed_date());
+    }
+
+    /// Hands out its data a few bytes per `read`.
+    struct Chunked<'a>(&'a [u8]);
+
+    impl Read for Chunked<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.0.len().min(buf.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_verify_request_streaming() {
+        let s = make_signer();
+        let body: Vec<u8> = (0..10_000u32).map(|x| (x % 251) as u8).collect();
+        let mut req = Request::builder()
+            .method("PUT")
+            .uri("https://oss.jdcloud-api.com/bucket/object")
+            .body(body.clone())
+            .unwrap();
+        s.sign_request(&mut req).unwrap();
+
+        let (parts, _) = req.into_parts();
+        let head = Request::from_parts(parts, ());
+        assert!(s.verify_request_streaming(&head, Chunked(&body)).unwrap());
+        assert!(!s.verify_request_streaming(&head, Chunked(&body[1..])).unwrap());
+    }
+
+    /// A small xorshift generator, so the cases are the same on every run.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 as usize
+        }
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[self.next() % items.len()]
+        }
+    }
+
+    #[test]
+    fn test_verify_request_roundtrip() {
+        let methods = ["GET", "POST", "PUT", "DELETE"];
+        let segments = ["v1", "regions", "cn-north-1", "a%20b", "%E4%B8%AD", "-_.~", "x+y"];
+        let words = ["", "1", "a%20b", "a+b", "%2F", "%25", "~", "%E4%B8%AD", "x=y"];
+        let values = ["v", " padded ", "a  b", "a\tb", "application/json; charset=utf-8", "1,2"];
+
+        let s = make_signer();
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..200 {
+            let mut uri = "https://vm.jdcloud-api.com".to_string();
+            for _ in 0..rng.next() % 4 {
+                uri.push('/');
+                uri.push_str(rng.pick(&segments));
+            }
+            for i in 0..rng.next() % 4 {
+                uri.push(if i == 0 { '?' } else { '&' });
+                uri.push_str(&format!("{}{}={}", rng.pick(&["a", "B", "c"]), rng.pick(&words), rng.pick(&words)));
+            }
+
+            let mut builder = Request::builder().method(rng.pick(&methods)).uri(uri.as_str());
+            for i in 0..rng.next() % 5 {
+                builder = builder.header(format!("x-header-{}", i % 3).as_str(), rng.pick(&values));
+            }
+            let body: Vec<u8> = (0..rng.next() % 64).map(|_| rng.next() as u8).collect();
+            let mut req = builder.body(body).unwrap();
+
+            s.sign_request(&mut req).unwrap();
+            assert!(s.verify_request(&req).unwrap(), "{:?}", req);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Replays the signing test vectors in `tests/vectors`. Each file gives a
+//! request, the inputs of its signature, and the expected canonical request,
+//! string to sign and signature, with a `source` saying where they came from.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use http::Request;
+use jdcloud_signer::{diff_canonical, Credential, NonceGenerator, Signer};
+use serde_json::Value;
+
+struct FixedNonce(String);
+
+impl NonceGenerator for FixedNonce {
+    fn generate(&self) -> String {
+        self.0.clone()
+    }
+}
+
+fn field<'a>(vector: &'a Value, name: &str) -> &'a str {
+    vector[name].as_str().unwrap_or_else(|| panic!("missing `{}`", name))
+}
+
+/// The first field of `vector` the crate disagrees on, with both values.
+fn check(vector: &Value) -> Result<(), String> {
+    field(vector, "source");
+    let now = NaiveDateTime::parse_from_str(field(vector, "date"), "%Y%m%dT%H%M%SZ").unwrap();
+    let now = Utc.from_utc_datetime(&now);
+    let signer = Signer::new(Credential::new(field(vector, "access_key"), field(vector, "secret_key")),
+                             field(vector, "service"), field(vector, "region"))
+        .with_nonce_generator(FixedNonce(field(vector, "nonce").to_string()));
+
+    let request = &vector["request"];
+    let mut builder = Request::builder().method(field(request, "method")).uri(field(request, "uri"));
+    for header in request["headers"].as_array().unwrap() {
+        builder = builder.header(header[0].as_str().unwrap(), header[1].as_str().unwrap());
+    }
+    let mut request = builder.body(field(request, "body").to_string()).unwrap();
+    signer.sign_request_with_time(&mut request, &now).map_err(|e| format!("signing failed: {}", e))?;
+
+    let expected = field(vector, "canonical_request");
+    let actual = signer.canonical_request(&request).unwrap();
+    if let Some(diff) = diff_canonical(expected, &actual) {
+        return Err(format!("canonical request diverges at {:?}: expected {:?}, got {:?}; expected:\n{}\ngot:\n{}",
+                           diff.field, diff.left, diff.right, expected, actual))
+    }
+    let expected = field(vector, "string_to_sign");
+    let (actual, _) = signer.string_to_sign(&request, &now).unwrap();
+    if actual != expected {
+        return Err(format!("string to sign diverges, expected:\n{}\ngot:\n{}", expected, actual))
+    }
+    let expected = field(vector, "signature");
+    let authorization = request.headers()["authorization"].to_str().unwrap();
+    let actual = authorization.rsplit("Signature=").next().unwrap();
+    if actual != expected {
+        return Err(format!("signature diverges, expected {}, got {}", expected, actual))
+    }
+    Ok(())
+}
+
+#[test]
+fn test_vectors() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+    let mut paths: Vec<_> = fs::read_dir(dir).unwrap()
+        .map(|x| x.unwrap().path())
+        .filter(|x| x.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    let mut failures = Vec::new();
+    for path in paths {
+        let vector: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        if let Err(e) = check(&vector) {
+            failures.push(format!("{}: {}", path.file_name().unwrap().to_string_lossy(), e));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
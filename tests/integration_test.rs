@@ -26,15 +26,14 @@ fn test_vm() {
     let credential = Credential::new(ak, sk);
     let signer = Signer::new(credential, "vm", "cn-north-1");
 
-    let mut req = Request::builder();
-    let mut req = req.method("GET")
+    let mut req = Request::builder().method("GET")
         .uri("https://vm.jdcloud-api.com/v1/regions/cn-north-1/instances")
         .body("".to_string()).unwrap();
     assert!(signer.sign_request(&mut req).unwrap());
     println!("{:?}", req);
 
     let client = Client::new();
-    let mut res = client.execute(req).unwrap();
+    let res = client.execute(req).unwrap();
 
     assert_eq!(res.status(), 200);
     for header in res.headers().into_iter() {